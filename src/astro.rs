@@ -1,3 +1,4 @@
+use crate::delta_t::delta_t;
 use crate::julian_time::JulianTime;
 use crate::moon::Phases;
 use crate::utils;
@@ -8,11 +9,88 @@ use chrono::{DateTime, Utc};
 //     Jean Meeus - Astronomical Algorithm 2nd Edition 1998
 //     Chapters 47 and 49
 
-/// Difference between uniform Astronomical Time and variable Earth Time (2026)
-const DELTA_T: f64 = 70.0;
+/// The Moon's mean distance from Earth, in kilometers.
+const MEAN_DISTANCE: f64 = 385_000.56;
 
 // ---------------- public ----------------
 
+/// The Moon's full geocentric position.
+#[derive(Debug, Clone, Copy)]
+pub struct LunarPosition {
+    /// Geocentric ecliptic longitude, in degrees (not corrected for nutation).
+    pub longitude: f64,
+    /// Geocentric ecliptic latitude, in degrees.
+    pub latitude: f64,
+    /// Distance between the centers of Earth and Moon, in kilometers.
+    pub distance: f64,
+}
+
+/// Computes the Moon's full geocentric position (longitude, latitude, distance) from the
+/// complete periodic series of Meeus chapter 47.
+///
+/// * `julian` - The Julian Time
+pub fn lunar_position(julian: &JulianTime) -> LunarPosition {
+    let t = julian.century;
+
+    let d = moon_elongation(julian);
+    let m = sun_mean_anomaly(julian);
+    let m_prime = moon_mean_anomaly(julian);
+    let f = moon_argument_of_latitude(julian);
+    let l_prime = moon_mean_longitude(julian);
+
+    // Eccentricity correction for the Earth's orbit, applied once (E) or twice (E²) to terms
+    // that carry the Sun's mean anomaly M.
+    let e = utils::polynomial_eval(t, &[1.0, -0.002516, -0.0000074]);
+
+    let mut sigma_l = 0.0;
+    let mut sigma_r = 0.0;
+    for &(di, mi, mpi, fi, coef_l, coef_r) in TABLE_LR {
+        let arg = (di as f64 * d + mi as f64 * m + mpi as f64 * m_prime + fi as f64 * f)
+            .to_radians();
+        let e_factor = match mi.abs() {
+            1 => e,
+            2 => e * e,
+            _ => 1.0,
+        };
+        sigma_l += coef_l * e_factor * arg.sin();
+        sigma_r += coef_r * e_factor * arg.cos();
+    }
+
+    let mut sigma_b = 0.0;
+    for &(di, mi, mpi, fi, coef_b) in TABLE_B {
+        let arg = (di as f64 * d + mi as f64 * m + mpi as f64 * m_prime + fi as f64 * f)
+            .to_radians();
+        let e_factor = match mi.abs() {
+            1 => e,
+            2 => e * e,
+            _ => 1.0,
+        };
+        sigma_b += coef_b * e_factor * arg.sin();
+    }
+
+    // Additive terms from Venus (A1), Jupiter (A2) and the solar excentricity (A3).
+    let a1 = utils::map_to_deg(119.75 + 131.849 * t).to_radians();
+    let a2 = utils::map_to_deg(53.09 + 479264.290 * t).to_radians();
+    let a3 = utils::map_to_deg(313.45 + 481266.484 * t).to_radians();
+    let l_prime_rad = l_prime.to_radians();
+    let f_rad = f.to_radians();
+    let m_prime_rad = m_prime.to_radians();
+
+    sigma_l += 3958.0 * a1.sin() + 1962.0 * (l_prime_rad - f_rad).sin() + 318.0 * a2.sin();
+    sigma_b += -2235.0 * l_prime_rad.sin()
+        + 382.0 * a3.sin()
+        + 175.0 * (a1 - f_rad).sin()
+        + 175.0 * (a1 + f_rad).sin()
+        + 127.0 * (l_prime_rad - m_prime_rad).sin()
+        - 115.0 * (l_prime_rad + m_prime_rad).sin();
+
+    LunarPosition {
+        longitude: utils::map_to_deg(l_prime + sigma_l / 1_000_000.0),
+        latitude: sigma_b / 1_000_000.0,
+        distance: MEAN_DISTANCE + sigma_r / 1000.0,
+    }
+}
+
 /// Computes the illuminated fraction.
 ///
 /// * `julian` - The Julian Time
@@ -74,6 +152,31 @@ pub fn phases_around(julian: &JulianTime) -> ((Phases, DateTime<Utc>), (Phases,
     (prev_phase, next_phase)
 }
 
+/// Enumerates every New Moon, First Quarter, Full Moon and Last Quarter event in `[start, end]`.
+///
+/// * `start` - The start of the range, inclusive
+/// * `end` - The end of the range, inclusive
+pub fn phases_between(start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<(Phases, DateTime<Utc>)> {
+    let k = index_lunation(&JulianTime::new(start));
+    let mut k = (k * 4.0).floor() / 4.0;
+
+    let mut events = Vec::new();
+    loop {
+        let event_time = JulianTime::to_utc(julian_day_of_phase(k));
+        if event_time > end {
+            break;
+        }
+        if event_time >= start {
+            let quarter = (k * 4.0).round() as i64;
+            let phase = Phases::from_int((quarter.rem_euclid(4) as u8 * 2) % 8).unwrap();
+            events.push((phase, event_time));
+        }
+        k += 0.25;
+    }
+
+    events
+}
+
 // ---------------- private ----------------
 
 /// Computes the Selenocentric Elongation in degrees.
@@ -93,7 +196,7 @@ fn selenocentric_elongation(d: f64, m: f64, m_prime: f64) -> f64 {
 /// Computes the sun mean anomaly in degrees.
 ///
 /// * `julian` - The Julian Time
-fn sun_mean_anomaly(julian: &JulianTime) -> f64 {
+pub(crate) fn sun_mean_anomaly(julian: &JulianTime) -> f64 {
     let t = julian.century;
     let coefs = [357.5291092, 35999.0502909, -0.0001536, 1.0 / 24490000.0];
     utils::map_to_deg(utils::polynomial_eval(t, &coefs))
@@ -102,7 +205,7 @@ fn sun_mean_anomaly(julian: &JulianTime) -> f64 {
 /// Computes the moon mean anomaly in degrees.
 ///
 /// * `julian` - The Julian Time
-fn moon_mean_anomaly(julian: &JulianTime) -> f64 {
+pub(crate) fn moon_mean_anomaly(julian: &JulianTime) -> f64 {
     let t = julian.century;
     let coefs = [
         134.9633964,
@@ -114,10 +217,174 @@ fn moon_mean_anomaly(julian: &JulianTime) -> f64 {
     utils::map_to_deg(utils::polynomial_eval(t, &coefs))
 }
 
+/// Computes the Moon's mean longitude in degrees.
+///
+/// * `julian` - The Julian Time
+fn moon_mean_longitude(julian: &JulianTime) -> f64 {
+    let t = julian.century;
+    let coefs = [
+        218.3164477,
+        481267.88123421,
+        -0.0015786,
+        1.0 / 538841.0,
+        -1.0 / 65194000.0,
+    ];
+    utils::map_to_deg(utils::polynomial_eval(t, &coefs))
+}
+
+/// Computes the Moon's mean argument of latitude (distance from its ascending node), in degrees.
+///
+/// * `julian` - The Julian Time
+fn moon_argument_of_latitude(julian: &JulianTime) -> f64 {
+    let t = julian.century;
+    let coefs = [
+        93.2720950,
+        483202.0175233,
+        -0.0036539,
+        -1.0 / 3526000.0,
+        1.0 / 863310000.0,
+    ];
+    utils::map_to_deg(utils::polynomial_eval(t, &coefs))
+}
+
+/// Periodic terms for the Moon's longitude and distance (Meeus Table 47.A).
+///
+/// Each row is `(D, M, M', F, coefficient of ΣL in 1e-6 degree, coefficient of ΣR in 1e-3 km)`.
+#[rustfmt::skip]
+const TABLE_LR: &[(i32, i32, i32, i32, f64, f64)] = &[
+    (0, 0, 1, 0, 6288774.0, -20905355.0),
+    (2, 0, -1, 0, 1274027.0, -3699111.0),
+    (2, 0, 0, 0, 658314.0, -2955968.0),
+    (0, 0, 2, 0, 213618.0, -569925.0),
+    (0, 1, 0, 0, -185116.0, 48888.0),
+    (0, 0, 0, 2, -114332.0, -3149.0),
+    (2, 0, -2, 0, 58793.0, 246158.0),
+    (2, -1, -1, 0, 57066.0, -152138.0),
+    (2, 0, 1, 0, 53322.0, -170733.0),
+    (2, -1, 0, 0, 45758.0, -204586.0),
+    (0, 1, -1, 0, -40923.0, -129620.0),
+    (1, 0, 0, 0, -34720.0, 108743.0),
+    (0, 1, 1, 0, -30383.0, 104755.0),
+    (2, 0, 0, -2, 15327.0, 10321.0),
+    (0, 0, 1, 2, -12528.0, 0.0),
+    (0, 0, 1, -2, 10980.0, 79661.0),
+    (4, 0, -1, 0, 10675.0, -34782.0),
+    (0, 0, 3, 0, 10034.0, -23210.0),
+    (4, 0, -2, 0, 8548.0, -21636.0),
+    (2, 1, -1, 0, -7888.0, 24208.0),
+    (2, 1, 0, 0, -6766.0, 30824.0),
+    (1, 0, -1, 0, -5163.0, -8379.0),
+    (1, 1, 0, 0, 4987.0, -16675.0),
+    (2, -1, 1, 0, 4036.0, -12831.0),
+    (2, 0, 2, 0, 3994.0, -10445.0),
+    (4, 0, 0, 0, 3861.0, -11650.0),
+    (2, 0, -3, 0, 3665.0, 14403.0),
+    (0, 1, -2, 0, -2689.0, -7003.0),
+    (2, 0, -1, 2, -2602.0, 0.0),
+    (2, -1, -2, 0, 2390.0, 10056.0),
+    (1, 0, 1, 0, -2348.0, 6322.0),
+    (2, -2, 0, 0, 2236.0, -9884.0),
+    (0, 1, 2, 0, -2120.0, 5751.0),
+    (0, 2, 0, 0, -2069.0, 0.0),
+    (2, -2, -1, 0, 2048.0, -4950.0),
+    (2, 0, 1, -2, -1773.0, 4130.0),
+    (2, 0, 0, 2, -1595.0, 0.0),
+    (4, -1, -1, 0, 1215.0, -3958.0),
+    (0, 0, 2, 2, -1110.0, 0.0),
+    (3, 0, -1, 0, -892.0, 3258.0),
+    (2, 1, 1, 0, -810.0, 2616.0),
+    (4, -1, -2, 0, 759.0, -1897.0),
+    (0, 2, -1, 0, -713.0, -2117.0),
+    (2, 2, -1, 0, -700.0, 2354.0),
+    (2, 1, -2, 0, 691.0, 0.0),
+    (2, -1, 0, -2, 596.0, 0.0),
+    (4, 0, 1, 0, 549.0, -1423.0),
+    (0, 0, 4, 0, 537.0, -1117.0),
+    (4, -1, 0, 0, 520.0, -1571.0),
+    (1, 0, -2, 0, -487.0, -1739.0),
+    (2, 1, 0, -2, -399.0, 0.0),
+    (0, 0, 2, -2, -381.0, -4421.0),
+    (1, 1, 1, 0, 351.0, 0.0),
+    (3, 0, -2, 0, -340.0, 0.0),
+    (4, 0, -3, 0, 330.0, 0.0),
+    (2, -1, 2, 0, 327.0, 0.0),
+    (0, 2, 1, 0, -323.0, 1165.0),
+    (1, 1, -1, 0, 299.0, 0.0),
+    (2, 0, 3, 0, 294.0, 0.0),
+    (2, 0, -1, -2, 0.0, 8752.0),
+];
+
+/// Periodic terms for the Moon's latitude (Meeus Table 47.B).
+///
+/// Each row is `(D, M, M', F, coefficient of ΣB in 1e-6 degree)`.
+#[rustfmt::skip]
+const TABLE_B: &[(i32, i32, i32, i32, f64)] = &[
+    (0, 0, 0, 1, 5128122.0),
+    (0, 0, 1, 1, 280602.0),
+    (0, 0, 1, -1, 277693.0),
+    (2, 0, 0, -1, 173237.0),
+    (2, 0, -1, 1, 55413.0),
+    (2, 0, -1, -1, 46271.0),
+    (2, 0, 0, 1, 32573.0),
+    (0, 0, 2, 1, 17198.0),
+    (2, 0, 1, -1, 9266.0),
+    (0, 0, 2, -1, 8822.0),
+    (2, -1, 0, -1, 8216.0),
+    (2, 0, -2, -1, 4324.0),
+    (2, 0, 1, 1, 4200.0),
+    (2, 1, 0, -1, -3359.0),
+    (2, -1, -1, 1, 2463.0),
+    (2, -1, 0, 1, 2211.0),
+    (2, -1, -1, -1, 2065.0),
+    (0, 1, -1, -1, -1870.0),
+    (4, 0, -1, -1, 1828.0),
+    (0, 1, 0, 1, -1794.0),
+    (0, 0, 0, 3, -1749.0),
+    (0, 1, -1, 1, -1565.0),
+    (1, 0, 0, 1, -1491.0),
+    (0, 1, 1, 1, -1475.0),
+    (0, 1, 1, -1, -1410.0),
+    (0, 1, 0, -1, -1344.0),
+    (1, 0, 0, -1, -1335.0),
+    (0, 0, 3, 1, 1107.0),
+    (4, 0, 0, -1, 1021.0),
+    (4, 0, -1, 1, 833.0),
+    (0, 0, 1, -3, 777.0),
+    (4, 0, -2, 1, 671.0),
+    (2, 0, 0, -3, 607.0),
+    (2, 0, 2, -1, 596.0),
+    (2, -1, 1, -1, 491.0),
+    (2, 0, -2, 1, -451.0),
+    (0, 0, 3, -1, 439.0),
+    (2, 0, 2, 1, 422.0),
+    (2, 0, -3, -1, 421.0),
+    (2, 1, -1, 1, -366.0),
+    (2, 1, 0, 1, -351.0),
+    (4, 0, 0, 1, 331.0),
+    (2, -1, 1, 1, 315.0),
+    (2, -2, 0, -1, 302.0),
+    (0, 0, 1, 3, -283.0),
+    (2, 1, 1, -1, -229.0),
+    (1, 1, 0, -1, 223.0),
+    (1, 1, 0, 1, 223.0),
+    (0, 1, -2, -1, -220.0),
+    (2, 1, -1, -1, -220.0),
+    (1, 0, 1, 1, -185.0),
+    (2, -1, -2, -1, 181.0),
+    (0, 1, 2, 1, -177.0),
+    (4, 0, -2, -1, 176.0),
+    (4, -1, -1, -1, 166.0),
+    (1, 0, 1, -1, -164.0),
+    (4, 0, 1, -1, 132.0),
+    (1, 0, -1, -1, -119.0),
+    (4, -1, 0, -1, 115.0),
+    (2, -2, 0, 1, 107.0),
+];
+
 /// Computes the julian ephemeris day.
 ///
 /// * `k` - THe index of lunation
-fn julian_day_of_phase(k: f64) -> f64 {
+pub(crate) fn julian_day_of_phase(k: f64) -> f64 {
     let t = k / 1236.85;
     let coefs = [
         2451550.09766 + 29.530588861 * k,
@@ -150,8 +417,22 @@ fn julian_day_of_phase(k: f64) -> f64 {
 
     let jde_true = jde_mean + correction;
 
-    // Convert to Universal Time
-    jde_true - DELTA_T / 86400.0
+    // Convert to Universal Time, using the year/month of this lunation to
+    // pick the right Delta T polynomial instead of a fixed offset.
+    let (year, month) = lunation_year_month(k);
+
+    jde_true - delta_t(year, month) / 86400.0
+}
+
+/// Estimates the calendar year and month of the k-th lunation, for picking a Delta T branch.
+///
+/// * `k` - The index of lunation
+fn lunation_year_month(k: f64) -> (f64, f64) {
+    // `k / 1236.85` is in Julian centuries (`t`), so the year offset from 2000 is `t * 100`.
+    let decimal_year = 2000.0 + (k / 1236.85) * 100.0;
+    let year = decimal_year.floor();
+    let month = ((decimal_year - year) * 12.0).floor() + 1.0;
+    (year, month)
 }
 
 /// Estimates k, the index of lunation.
@@ -163,3 +444,65 @@ fn index_lunation(julian: &JulianTime) -> f64 {
     let avg_synodic_month = 29.530588861;
     (jd - ref_new_moon) / avg_synodic_month
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn lunation_year_month_tracks_real_year() {
+        let lunations_per_year = 1236.85 / 100.0;
+        for year in (1700..=2150).step_by(50) {
+            let k = (year as f64 - 2000.0) * lunations_per_year;
+            let (estimated_year, _) = lunation_year_month(k);
+            assert!(
+                (estimated_year - year as f64).abs() <= 1.0,
+                "k = {k} (real year {year}) produced estimated year {estimated_year}"
+            );
+        }
+    }
+
+    /// Meeus example 47.a: 1992 April 12.0 TD, JDE 2448724.5.
+    #[test]
+    fn lunar_position_matches_meeus_example() {
+        let jd = 2448724.5;
+        let julian = JulianTime {
+            utc: Utc.with_ymd_and_hms(1992, 4, 12, 0, 0, 0).unwrap(),
+            day: jd,
+            century: (jd - 2451545.0) / 36525.0,
+        };
+
+        let position = lunar_position(&julian);
+
+        assert!((position.longitude - 133.162655).abs() < 0.001);
+        assert!((position.latitude - (-3.229126)).abs() < 0.001);
+        assert!((position.distance - 368409.7).abs() < 1.0);
+    }
+
+    #[test]
+    fn phases_between_finds_known_january_2024_events() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+
+        let events = phases_between(start, end);
+
+        let phases: Vec<_> = events.iter().map(|(phase, _)| phase.to_string()).collect();
+        assert_eq!(
+            phases,
+            vec!["Last Quarter", "New Moon", "First Quarter", "Full Moon"]
+        );
+        for (_, time) in &events {
+            assert!(*time >= start && *time <= end);
+        }
+    }
+
+    #[test]
+    fn phases_between_is_empty_for_a_degenerate_or_inverted_range() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+
+        assert!(phases_between(start, start).is_empty());
+        assert!(phases_between(end, start).is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::utils::polynomial_eval;
+
+// This part is based on:
+//     Espenak & Meeus - Five Millennium Canon of Solar Eclipses (2006)
+//     "Polynomial Expressions for Delta T"
+
+/// Computes ΔT, the difference between Dynamical Time and Universal Time, in seconds.
+///
+/// * `year` - The calendar year
+/// * `month` - The calendar month (1-12)
+pub fn delta_t(year: f64, month: f64) -> f64 {
+    let y = year + (month - 0.5) / 12.0;
+
+    if y < -500.0 {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u.powi(2)
+    } else if y < 500.0 {
+        let u = y / 100.0;
+        polynomial_eval(
+            u,
+            &[
+                10583.6,
+                -1014.41,
+                33.78311,
+                -5.952053,
+                -0.1798452,
+                0.022174192,
+                0.0090316521,
+            ],
+        )
+    } else if y < 1600.0 {
+        let u = (y - 1000.0) / 100.0;
+        polynomial_eval(
+            u,
+            &[
+                1574.2,
+                -556.01,
+                71.23472,
+                0.319781,
+                -0.8503463,
+                -0.005050998,
+                0.0083572073,
+            ],
+        )
+    } else if y < 1700.0 {
+        let t = y - 1600.0;
+        polynomial_eval(t, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0])
+    } else if y < 1800.0 {
+        let t = y - 1700.0;
+        polynomial_eval(
+            t,
+            &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1174000.0],
+        )
+    } else if y < 1860.0 {
+        let t = y - 1800.0;
+        polynomial_eval(
+            t,
+            &[
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ],
+        )
+    } else if y < 1900.0 {
+        let t = y - 1860.0;
+        polynomial_eval(
+            t,
+            &[7.62, 0.5737, -0.251754, 0.01680668, -0.0004473624, 1.0 / 233174.0],
+        )
+    } else if y < 1920.0 {
+        let t = y - 1900.0;
+        polynomial_eval(t, &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197])
+    } else if y < 1941.0 {
+        let t = y - 1920.0;
+        polynomial_eval(t, &[21.20, 0.84493, -0.0761, 0.0020936])
+    } else if y < 1961.0 {
+        let t = y - 1950.0;
+        polynomial_eval(t, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0])
+    } else if y < 1986.0 {
+        let t = y - 1975.0;
+        polynomial_eval(t, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0])
+    } else if y < 2005.0 {
+        let t = y - 2000.0;
+        polynomial_eval(
+            t,
+            &[63.86, 0.3345, -0.060374, 0.0017275, 0.000651814, 0.00002373599],
+        )
+    } else if y < 2050.0 {
+        let t = y - 2000.0;
+        polynomial_eval(t, &[62.92, 0.32217, 0.005589])
+    } else if y <= 2150.0 {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u.powi(2) - 0.5628 * (2150.0 - y)
+    } else {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u.powi(2)
+    }
+}
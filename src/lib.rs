@@ -0,0 +1,7 @@
+pub mod astro;
+pub mod delta_t;
+pub mod julian_time;
+pub mod location;
+pub mod lunar_calendar;
+pub mod moon;
+pub mod utils;
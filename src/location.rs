@@ -0,0 +1,309 @@
+use crate::astro;
+use crate::julian_time::JulianTime;
+use crate::utils;
+
+use chrono::{DateTime, Utc};
+
+// This part is based on:
+//     Jean Meeus - Astronomical Algorithm 2nd Edition 1998
+//     Chapters 12, 13 and 47
+
+/// Standard altitude of the Moon at the horizon, accounting for parallax and refraction.
+const STANDARD_ALTITUDE: f64 = 0.125;
+
+/// An observer's position on Earth.
+#[derive(Debug, Clone, Copy)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Elevation above sea level, in meters. Raises the visible horizon's dip, so
+    /// `moonrise`/`moonset` happen slightly earlier/later than at sea level.
+    pub elevation: f64,
+}
+
+impl Location {
+    /// Builds a new observer location.
+    ///
+    /// * `latitude` - The latitude in degrees, positive north
+    /// * `longitude` - The longitude in degrees, positive east
+    /// * `elevation` - The elevation above sea level, in meters
+    pub fn new(latitude: f64, longitude: f64, elevation: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            elevation,
+        }
+    }
+}
+
+// ---------------- public ----------------
+
+/// Computes the Moon's true geometric altitude above the astronomical horizon, in degrees.
+///
+/// This does not account for `location`'s elevation: the dip of the visible horizon only
+/// matters when deciding whether the Moon has risen or set, which `moonrise`/`moonset` handle.
+///
+/// * `julian` - The Julian Time
+/// * `location` - The observer's location
+pub fn lunar_altitude(julian: &JulianTime, location: &Location) -> f64 {
+    altitude_at(julian.day, location)
+}
+
+/// Computes the moonrise preceding the Moon's first transit at or after `julian`, at
+/// `location`, or `None` if the Moon does not rise in the searched window (e.g. near the poles,
+/// where it can stay below or above the horizon for several days).
+///
+/// When the Moon is already above the horizon at `julian`, the returned moonrise is earlier
+/// than `julian`.
+///
+/// * `julian` - The Julian Time
+/// * `location` - The observer's location
+pub fn moonrise(julian: &JulianTime, location: &Location) -> Option<DateTime<Utc>> {
+    find_crossing(julian.day, location, true)
+}
+
+/// Computes the moonset following the Moon's first transit at or after `julian`, at
+/// `location`, or `None` if the Moon does not set in the searched window (e.g. near the poles,
+/// where it can stay below or above the horizon for several days).
+///
+/// When the Moon has already transited but not yet set at `julian`, that moonset is not
+/// returned; the one following the next transit is.
+///
+/// * `julian` - The Julian Time
+/// * `location` - The observer's location
+pub fn moonset(julian: &JulianTime, location: &Location) -> Option<DateTime<Utc>> {
+    find_crossing(julian.day, location, false)
+}
+
+// ---------------- private ----------------
+
+/// How many apparent lunar days to search before concluding there is no rise/set event.
+const MAX_SEARCH_DAYS: u32 = 30;
+
+/// Computes the geometric altitude, in degrees, a rising or setting Moon must cross as seen
+/// from `location`: the standard lunar horizon, adjusted for the dip of the horizon at
+/// `location`'s elevation.
+///
+/// * `location` - The observer's location
+fn rise_set_target_altitude(location: &Location) -> f64 {
+    STANDARD_ALTITUDE - horizon_dip(location.elevation)
+}
+
+/// Computes the dip of the horizon below the astronomical horizontal, in degrees, for an
+/// observer at `elevation` meters above sea level.
+///
+/// * `elevation` - The observer's elevation above sea level, in meters
+fn horizon_dip(elevation: f64) -> f64 {
+    if elevation <= 0.0 {
+        0.0
+    } else {
+        0.0293 * elevation.sqrt()
+    }
+}
+
+/// Computes the Moon's geometric altitude above the horizon, in degrees, at a given Julian Day.
+///
+/// * `jd` - The Julian Day
+/// * `location` - The observer's location
+fn altitude_at(jd: f64, location: &Location) -> f64 {
+    let julian = JulianTime::new(JulianTime::to_utc(jd));
+    let (ra, dec) = equatorial_coordinates(&julian);
+    let h = local_hour_angle(&julian, location.longitude, ra).to_radians();
+
+    let lat = location.latitude.to_radians();
+    let dec = dec.to_radians();
+
+    let sin_altitude = lat.sin() * dec.sin() + lat.cos() * dec.cos() * h.cos();
+    sin_altitude.asin().to_degrees()
+}
+
+/// Finds the moonrise (`rising = true`) or moonset (`rising = false`) around the Moon's first
+/// transit at or after `jd`, by bisecting the altitude over the half lunar day before (rise) or
+/// after (set) that transit. Returns `None` if no crossing of
+/// the target altitude is found within `MAX_SEARCH_DAYS` apparent lunar days, meaning the Moon
+/// does not rise/set in that window at `location`.
+///
+/// * `jd` - The Julian Day to search from
+/// * `location` - The observer's location
+/// * `rising` - Whether to search for the rising (`true`) or the setting (`false`) edge
+fn find_crossing(jd: f64, location: &Location, rising: bool) -> Option<DateTime<Utc>> {
+    let target = rise_set_target_altitude(location);
+    let mut transit = find_transit(jd, location);
+
+    // One apparent lunar day is a bit longer than a solar day; step forward until the
+    // transit is at or after `jd`, then keep stepping until a bracket actually contains a
+    // crossing or we give up.
+    for _ in 0..MAX_SEARCH_DAYS {
+        let (lo, hi) = if rising {
+            (transit - 0.52, transit)
+        } else {
+            (transit, transit + 0.52)
+        };
+
+        let bracket_end = if rising { hi } else { lo };
+        if bracket_end >= jd {
+            if let Some(crossing) = bisect_altitude(lo, hi, target, location) {
+                return Some(JulianTime::to_utc(crossing));
+            }
+        }
+
+        transit += 1.0350;
+    }
+
+    None
+}
+
+/// Bisects `[lo, hi]` for the Julian Day where the Moon's altitude crosses `target`, or `None`
+/// if the altitude at both ends is on the same side of `target` (no crossing in this bracket).
+///
+/// * `lo` - The start of the bracket, as a Julian Day
+/// * `hi` - The end of the bracket, as a Julian Day
+/// * `target` - The altitude to solve for, in degrees
+/// * `location` - The observer's location
+fn bisect_altitude(mut lo: f64, mut hi: f64, target: f64, location: &Location) -> Option<f64> {
+    let sign_lo = (altitude_at(lo, location) - target).signum();
+    let sign_hi = (altitude_at(hi, location) - target).signum();
+    if sign_lo == sign_hi {
+        return None;
+    }
+
+    for _ in 0..40 {
+        let mid = (lo + hi) / 2.0;
+        let sign_mid = (altitude_at(mid, location) - target).signum();
+        if sign_mid != sign_lo {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Some((lo + hi) / 2.0)
+}
+
+/// Finds the Julian Day of the Moon's transit (local hour angle `H = 0`) nearest to `jd`.
+///
+/// * `jd` - The Julian Day to search from
+/// * `location` - The observer's location
+fn find_transit(jd: f64, location: &Location) -> f64 {
+    let mut jd = jd;
+    // The hour angle advances by roughly 347.8 degrees per day (sidereal rotation minus the
+    // Moon's own orbital motion); a handful of linear corrections converges on H = 0.
+    for _ in 0..5 {
+        let julian = JulianTime::new(JulianTime::to_utc(jd));
+        let (ra, _) = equatorial_coordinates(&julian);
+        let h = local_hour_angle(&julian, location.longitude, ra);
+        let h_signed = ((h + 180.0) % 360.0) - 180.0;
+        jd -= h_signed / 347.8;
+    }
+    jd
+}
+
+/// Computes the local hour angle of the Moon, in degrees.
+///
+/// * `julian` - The Julian Time
+/// * `longitude` - The observer's longitude in degrees, positive east
+/// * `right_ascension` - The Moon's right ascension in degrees
+fn local_hour_angle(julian: &JulianTime, longitude: f64, right_ascension: f64) -> f64 {
+    utils::map_to_deg(mean_sidereal_time(julian) + longitude - right_ascension)
+}
+
+/// Computes the mean sidereal time at Greenwich, in degrees (Meeus 12.4). This omits the
+/// equation of the equinoxes, so it is not corrected for nutation.
+///
+/// * `julian` - The Julian Time
+fn mean_sidereal_time(julian: &JulianTime) -> f64 {
+    let t = julian.century;
+    let coefs = [
+        280.46061837,
+        360.98564736629 * 36525.0,
+        0.000387933,
+        -1.0 / 38710000.0,
+    ];
+    utils::map_to_deg(utils::polynomial_eval(t, &coefs))
+}
+
+/// Computes the Moon's right ascension and declination, in degrees.
+///
+/// * `julian` - The Julian Time
+fn equatorial_coordinates(julian: &JulianTime) -> (f64, f64) {
+    let position = astro::lunar_position(julian);
+    let obliquity = obliquity_of_ecliptic(julian);
+
+    let lon = position.longitude.to_radians();
+    let lat = position.latitude.to_radians();
+    let eps = obliquity.to_radians();
+
+    let ra = (lon.sin() * eps.cos() - lat.tan() * eps.sin()).atan2(lon.cos());
+    let dec = (lat.sin() * eps.cos() + lat.cos() * eps.sin() * lon.sin()).asin();
+
+    (utils::map_to_deg(ra.to_degrees()), dec.to_degrees())
+}
+
+/// Computes the obliquity of the ecliptic, in degrees.
+///
+/// * `julian` - The Julian Time
+fn obliquity_of_ecliptic(julian: &JulianTime) -> f64 {
+    23.4393 - 0.0130042 * julian.century
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn julian_at(year: i32, month: u32, day: u32) -> JulianTime {
+        JulianTime::new(Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn lunar_altitude_is_a_plausible_angle() {
+        let paris = Location::new(48.8566, 2.3522, 35.0);
+        let altitude = lunar_altitude(&julian_at(2024, 1, 1), &paris);
+        assert!((-90.0..=90.0).contains(&altitude));
+    }
+
+    #[test]
+    fn moonrise_precedes_moonset_on_a_typical_day() {
+        let paris = Location::new(48.8566, 2.3522, 35.0);
+        let julian = julian_at(2024, 1, 1);
+
+        let rise = moonrise(&julian, &paris).expect("the Moon rises at this latitude");
+        let set = moonset(&julian, &paris).expect("the Moon sets at this latitude");
+        assert!(rise < set);
+    }
+
+    #[test]
+    fn higher_elevation_brings_moonrise_forward() {
+        let sea_level = Location::new(48.8566, 2.3522, 0.0);
+        let mountain = Location::new(48.8566, 2.3522, 4000.0);
+        let julian = julian_at(2024, 1, 1);
+
+        let rise_sea_level = moonrise(&julian, &sea_level).unwrap();
+        let rise_mountain = moonrise(&julian, &mountain).unwrap();
+        assert!(rise_mountain <= rise_sea_level);
+    }
+
+    #[test]
+    fn moonrise_is_none_during_a_circumpolar_stretch() {
+        // Near the pole, the Moon can stay below the horizon for days at a time.
+        let pole = Location::new(89.5, 0.0, 0.0);
+        let julian = julian_at(2024, 1, 17);
+        assert_eq!(moonrise(&julian, &pole), None);
+    }
+
+    #[test]
+    fn bisect_altitude_returns_none_without_a_crossing() {
+        let paris = Location::new(48.8566, 2.3522, 35.0);
+        // Both ends of a 0.01-day bracket land on the same side of an altitude the Moon
+        // never reaches at this latitude that day.
+        let julian = julian_at(2024, 1, 1);
+        assert_eq!(bisect_altitude(julian.day, julian.day + 0.01, 90.0, &paris), None);
+    }
+
+    #[test]
+    fn horizon_dip_grows_with_elevation_and_is_zero_at_sea_level() {
+        assert_eq!(horizon_dip(0.0), 0.0);
+        assert_eq!(horizon_dip(-10.0), 0.0);
+        assert!(horizon_dip(4000.0) > horizon_dip(1000.0));
+    }
+}
@@ -0,0 +1,308 @@
+use crate::astro;
+use crate::julian_time::JulianTime;
+use crate::utils;
+
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+
+// This part is based on:
+//     Ho Ngoc Duc - Calculation of the Vietnamese lunisolar calendar (the "amlich" algorithm),
+//     itself built on Jean Meeus - Astronomical Algorithm 2nd Edition 1998, Chapters 25 and 49
+
+/// The Julian Day of the reference new moon (lunation 0) that `julian_day_of_phase` is
+/// anchored on.
+const REF_INDEX_EPOCH: f64 = 2451550.09766;
+/// The average synodic month, in days.
+const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// A date on the lunisolar calendar.
+///
+/// Months are numbered from 1 to 12 starting at the 11th month anchored to the
+/// winter-solstice new moon; `leap` flags a 13th, inserted month that carries no
+/// major solar term (zhongqi).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LunarDate {
+    pub day: u32,
+    pub month: u32,
+    pub year: i32,
+    pub leap: bool,
+}
+
+// ---------------- public ----------------
+
+/// Converts a Gregorian calendar date to its lunisolar equivalent.
+///
+/// * `date` - The Gregorian calendar date
+/// * `tz_offset_hours` - The observer's timezone offset from UTC, in hours
+pub fn gregorian_to_lunar(date: NaiveDate, tz_offset_hours: f64) -> LunarDate {
+    let today = day_number(date);
+    let mut k = estimate_new_moon_index(today);
+
+    let mut month_start = new_moon_day(k + 1.0, tz_offset_hours);
+    if month_start > today {
+        month_start = new_moon_day(k, tz_offset_hours);
+        // The estimate can still land a local day late when the true new moon falls right at
+        // local midnight and `new_moon_day`'s `floor()` rounds it past `today`; keep walking
+        // back until we actually land on or before `today`.
+        while month_start > today {
+            k -= 1.0;
+            month_start = new_moon_day(k, tz_offset_hours);
+        }
+    }
+
+    let mut a11 = lunar_month_11(date.year(), tz_offset_hours);
+    let mut b11 = a11;
+    let mut lunar_year = date.year();
+    if a11 >= month_start {
+        a11 = lunar_month_11(date.year() - 1, tz_offset_hours);
+    } else {
+        lunar_year += 1;
+        b11 = lunar_month_11(date.year() + 1, tz_offset_hours);
+    }
+
+    let diff = ((month_start - a11) / 29.0).floor() as i32;
+    let mut lunar_month = diff + 11;
+    let mut leap = false;
+
+    if b11 - a11 > 365.0 {
+        let leap_month_offset = leap_month_offset(a11, tz_offset_hours);
+        if diff >= leap_month_offset {
+            lunar_month = diff + 10;
+            leap = diff == leap_month_offset;
+        }
+    }
+
+    if lunar_month > 12 {
+        lunar_month -= 12;
+    }
+    if lunar_month >= 11 && diff < 4 {
+        lunar_year -= 1;
+    }
+
+    LunarDate {
+        day: (today - month_start + 1.0) as u32,
+        month: lunar_month as u32,
+        year: lunar_year,
+        leap,
+    }
+}
+
+/// Converts a lunisolar date back to its Gregorian equivalent.
+///
+/// * `date` - The lunisolar date
+/// * `tz_offset_hours` - The observer's timezone offset from UTC, in hours
+pub fn lunar_to_gregorian(date: LunarDate, tz_offset_hours: f64) -> NaiveDate {
+    let (a11, b11) = if date.month < 11 {
+        (
+            lunar_month_11(date.year - 1, tz_offset_hours),
+            lunar_month_11(date.year, tz_offset_hours),
+        )
+    } else {
+        (
+            lunar_month_11(date.year, tz_offset_hours),
+            lunar_month_11(date.year + 1, tz_offset_hours),
+        )
+    };
+
+    let k = (0.5 + (a11 - REF_INDEX_EPOCH) / SYNODIC_MONTH).floor();
+
+    let mut month_offset = date.month as i32 - 11;
+    if month_offset < 0 {
+        month_offset += 12;
+    }
+
+    if b11 - a11 > 365.0 {
+        let leap_offset = leap_month_offset(a11, tz_offset_hours);
+        let leap_month = {
+            let m = leap_offset - 2;
+            if m < 0 { m + 12 } else { m }
+        };
+        // A requested leap month only exists if it matches the actual inserted month.
+        let valid_leap = !date.leap || date.month as i32 == leap_month;
+        if valid_leap && (date.leap || month_offset >= leap_offset) {
+            month_offset += 1;
+        }
+    }
+
+    let month_start = new_moon_day(k + month_offset as f64, tz_offset_hours);
+    date_from_day_number(month_start + date.day as f64 - 1.0)
+}
+
+// ---------------- private ----------------
+
+/// Finds the new moon anchoring the 11th lunar month of `year` (the new moon on or before the
+/// winter solstice), as a local day number.
+///
+/// * `year` - The Gregorian year
+/// * `tz_offset_hours` - The observer's timezone offset from UTC, in hours
+fn lunar_month_11(year: i32, tz_offset_hours: f64) -> f64 {
+    let dec31 = day_number(NaiveDate::from_ymd_opt(year, 12, 31).expect("valid date"));
+    let k = estimate_new_moon_index(dec31);
+
+    let mut new_moon = new_moon_day(k, tz_offset_hours);
+    if sun_longitude_term(new_moon, tz_offset_hours) >= 9 {
+        new_moon = new_moon_day(k - 1.0, tz_offset_hours);
+    }
+    new_moon
+}
+
+/// Finds how many lunations past `a11` the inserted leap month (the one spanning no major
+/// solar term) falls, by walking new moons until two in a row share the same term.
+///
+/// * `a11` - The local day number of the winter-solstice new moon
+/// * `tz_offset_hours` - The observer's timezone offset from UTC, in hours
+fn leap_month_offset(a11: f64, tz_offset_hours: f64) -> i32 {
+    let k = (0.5 + (a11 - REF_INDEX_EPOCH) / SYNODIC_MONTH).floor();
+
+    let mut i = 1;
+    let mut arc = sun_longitude_term(new_moon_day(k + i as f64, tz_offset_hours), tz_offset_hours);
+    loop {
+        let last = arc;
+        i += 1;
+        arc = sun_longitude_term(new_moon_day(k + i as f64, tz_offset_hours), tz_offset_hours);
+        if arc == last || i >= 14 {
+            break;
+        }
+    }
+    i - 1
+}
+
+/// Estimates the lunation index whose new moon is on or just before the local day `today`.
+///
+/// * `today` - The local day number to search from
+fn estimate_new_moon_index(today: f64) -> f64 {
+    ((today - REF_INDEX_EPOCH) / SYNODIC_MONTH).floor()
+}
+
+/// Computes the local day number of the k-th new moon.
+///
+/// * `k` - The index of lunation
+/// * `tz_offset_hours` - The observer's timezone offset from UTC, in hours
+fn new_moon_day(k: f64, tz_offset_hours: f64) -> f64 {
+    (astro::julian_day_of_phase(k) + 0.5 + tz_offset_hours / 24.0).floor()
+}
+
+/// Computes which of the 12 principal solar terms (zhongqi) the Sun occupies on a given local
+/// day number, numbered 0-11 starting at the March equinox.
+///
+/// * `day_number` - The local day number
+/// * `tz_offset_hours` - The observer's timezone offset from UTC, in hours
+fn sun_longitude_term(day_number: f64, tz_offset_hours: f64) -> i32 {
+    let jd = day_number - tz_offset_hours / 24.0;
+    let julian = JulianTime::new(JulianTime::to_utc(jd));
+    let t = julian.century;
+
+    let mean_longitude = utils::map_to_deg(utils::polynomial_eval(
+        t,
+        &[280.46646, 36000.76983, 0.0003032],
+    ));
+    let m = astro::sun_mean_anomaly(&julian).to_radians();
+
+    let center = (1.914602 - 0.004817 * t - 0.000014 * t.powi(2)) * m.sin()
+        + (0.019993 - 0.000101 * t) * (2.0 * m).sin()
+        + 0.000289 * (3.0 * m).sin();
+
+    let true_longitude = utils::map_to_deg(mean_longitude + center);
+    ((true_longitude / 30.0).floor() as i32).rem_euclid(12)
+}
+
+/// Computes the local day number (an integer-valued Julian Day) of a Gregorian calendar date.
+///
+/// * `date` - The Gregorian calendar date
+fn day_number(date: NaiveDate) -> f64 {
+    let midnight = Utc
+        .from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("valid time of day"));
+    (JulianTime::new(midnight).day + 0.5).floor()
+}
+
+/// Converts a local day number back to a Gregorian calendar date.
+///
+/// * `day_number` - The local day number
+fn date_from_day_number(day_number: f64) -> NaiveDate {
+    JulianTime::to_utc(day_number - 0.5).date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    /// Known Tết (Vietnamese New Year) dates, i.e. lunar month 1, day 1.
+    #[test]
+    fn gregorian_to_lunar_matches_known_tet_dates() {
+        let tet_dates = [
+            (2020, 1, 25),
+            (2023, 1, 22),
+            (2024, 2, 10),
+            (2025, 1, 29),
+        ];
+        for (year, month, day) in tet_dates {
+            let lunar = gregorian_to_lunar(ymd(year, month, day), 7.0);
+            assert_eq!(
+                lunar,
+                LunarDate {
+                    day: 1,
+                    month: 1,
+                    year,
+                    leap: false
+                },
+                "Tet {year}-{month}-{day}"
+            );
+        }
+    }
+
+    #[test]
+    fn gregorian_to_lunar_detects_the_2020_leap_month() {
+        // 2020 carries a leap month inserted right after lunar month 5.
+        let regular = gregorian_to_lunar(ymd(2020, 5, 23), 7.0);
+        assert!(!regular.leap);
+
+        let leap = gregorian_to_lunar(ymd(2020, 6, 21), 7.0);
+        assert!(leap.leap);
+        assert_eq!(leap.day, 1);
+    }
+
+    #[test]
+    fn gregorian_to_lunar_never_produces_a_day_zero() {
+        // Regression test: a new moon landing right at local midnight used to make the
+        // fallback in `gregorian_to_lunar` overshoot `today` and report `day: 0`.
+        for ordinal in 1..=366u32 {
+            for &tz_offset_hours in &[-12.0, 0.0, 7.0, 12.0] {
+                if let Some(date) = NaiveDate::from_yo_opt(2020, ordinal) {
+                    let lunar = gregorian_to_lunar(date, tz_offset_hours);
+                    assert!(
+                        lunar.day >= 1,
+                        "{date} at tz_offset_hours={tz_offset_hours} produced day {}",
+                        lunar.day
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gregorian_to_lunar_can_round_a_civil_day_differently_per_timezone() {
+        // Near a month boundary, the same UTC instant's civil day can fall on either side of a
+        // new moon depending on the observer's timezone offset.
+        let date = ymd(2020, 5, 22);
+        let before_new_moon = gregorian_to_lunar(date, 7.0);
+        assert_eq!(before_new_moon.month, 4);
+        assert_eq!(before_new_moon.day, 30);
+
+        let after_new_moon = gregorian_to_lunar(date, 0.0);
+        assert_eq!(after_new_moon.month, 5);
+        assert_eq!(after_new_moon.day, 1);
+    }
+
+    #[test]
+    fn lunar_to_gregorian_round_trips_through_gregorian_to_lunar() {
+        let start = ymd(2024, 1, 1);
+        for offset in 0..400 {
+            let date = start + chrono::Duration::days(offset);
+            let lunar = gregorian_to_lunar(date, 7.0);
+            assert_eq!(lunar_to_gregorian(lunar, 7.0), date, "round trip for {date}");
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::astro;
 use crate::julian_time::JulianTime;
 
-use chrono::Utc;
+use chrono::{DateTime, TimeZone, Utc};
 
 #[derive(Debug)]
 pub struct Lune {
@@ -13,10 +13,32 @@ pub struct Lune {
     delta_prev: f64,
 }
 
+impl Default for Lune {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Lune {
+    /// Builds a `Lune` for the current instant, in UTC.
     pub fn new() -> Self {
-        let now = Utc::now();
-        let julian = JulianTime::new(now);
+        Self::at(Utc::now())
+    }
+
+    /// Builds a `Lune` for the current instant, with day counts rounded against `tz`'s civil
+    /// midnight instead of UTC's.
+    ///
+    /// * `tz` - The observer's timezone
+    pub fn now_local<Tz: TimeZone>(tz: Tz) -> Self {
+        Self::at(Utc::now().with_timezone(&tz))
+    }
+
+    /// Builds a `Lune` for an arbitrary date and time, with day counts rounded against that
+    /// date's own timezone rather than assumed to be UTC.
+    ///
+    /// * `dt` - The date and time to compute the Moon's state for
+    pub fn at<Tz: TimeZone>(dt: DateTime<Tz>) -> Self {
+        let julian = JulianTime::new(dt.with_timezone(&Utc));
 
         let fraction = astro::illuminated_fraction(&julian);
         let phase = astro::get_phase(&julian);
@@ -27,13 +49,20 @@ impl Lune {
         let next_phase = around.1.0;
         let next_ts = around.1.1;
 
+        // Round day counts against this timezone's civil midnight, not a raw 24h duration,
+        // so users away from UTC don't see an off-by-one on the summary strings.
+        let tz = dt.timezone();
+        let today = dt.date_naive();
+        let next_day = next_ts.with_timezone(&tz).date_naive();
+        let prev_day = prev_ts.with_timezone(&tz).date_naive();
+
         Self {
             fraction,
             current_phase: Phases::from_int(phase).unwrap(),
             next_phase,
             prev_phase,
-            delta_next: (next_ts.signed_duration_since(now).num_hours() as f64 / 24.0).round(),
-            delta_prev: -(prev_ts.signed_duration_since(now).num_hours() as f64 / 24.0).round(),
+            delta_next: (next_day - today).num_days() as f64,
+            delta_prev: (today - prev_day).num_days() as f64,
         }
     }
 
@@ -117,3 +146,42 @@ impl std::fmt::Display for Phases {
         write!(f, "{}", s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    #[test]
+    fn at_identifies_a_known_new_moon() {
+        // 2024-01-11T11:57Z was a New Moon.
+        let dt = Utc.with_ymd_and_hms(2024, 1, 11, 12, 0, 0).unwrap();
+        let lune = Lune::at(dt);
+        assert_eq!(lune.get_current_phase(), "New Moon (0.0%)");
+    }
+
+    #[test]
+    fn at_rounds_day_deltas_against_the_given_timezone_civil_day() {
+        // Same instant, an hour before UTC midnight: in UTC it's still "today", but 8
+        // hours east it has already rolled over to the next civil day.
+        let dt = Utc.with_ymd_and_hms(2024, 1, 11, 23, 0, 0).unwrap();
+        let east_8 = FixedOffset::east_opt(8 * 3600).unwrap();
+
+        let utc_lune = Lune::at(dt);
+        let local_lune = Lune::at(dt.with_timezone(&east_8));
+
+        assert_ne!(
+            utc_lune.get_phase_summary(),
+            local_lune.get_phase_summary(),
+            "the same instant should round to a different civil day in each timezone"
+        );
+    }
+
+    #[test]
+    fn now_local_matches_at_for_the_same_instant_and_timezone() {
+        let tz = FixedOffset::east_opt(-5 * 3600).unwrap();
+        let now_local = Lune::now_local(tz);
+        let at_now = Lune::at(Utc::now().with_timezone(&tz));
+        assert_eq!(now_local.get_summary(), at_now.get_summary());
+    }
+}